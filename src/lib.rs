@@ -0,0 +1,885 @@
+// Copyright (c) 2025 Robert August Vincent II <pillarsdotnet@gmail.com>
+
+//! # timesheet_core — the engine behind `ts`
+//!
+//! Log parsing, START/STOP pairing, aggregation and time parsing for the `ts` timesheet log, for
+//! tools (GUIs, web frontends) that want to read the log without shelling out to the binary.
+//!
+//! - [`Entry`] is one line of the log, with its user tag, parallel flag and activity.
+//! - [`LogLine`] is the main-track view of an entry that [`log_sessions`] pairs (LIFO) into
+//!   [`Session`]s; parallel timers pair separately ([`ParallelEvent`], [`parallel_sessions`]).
+//! - [`Report`] totals sessions per activity code and per day.
+//! - [`parse_timestamp`], [`parse_start_time`] and [`parse_interval_duration`] parse the times and
+//!   durations the CLI accepts.
+//!
+//! ```
+//! use timesheet_core::{log_sessions, parse_line_for_user, Report};
+//!
+//! let log = "2026-10-14T09:00:00-07:00|START|DEV|billing\n2026-10-14T10:30:00-07:00|STOP\n";
+//! let lines: Vec<_> = log
+//!     .lines()
+//!     .enumerate()
+//!     .filter_map(|(i, l)| parse_line_for_user(l, None).map(|ll| (i + 1, ll)))
+//!     .collect();
+//! let report = Report::from_sessions(&log_sessions(&lines, None));
+//! assert_eq!(report.by_activity["DEV"], 5400);
+//! ```
+//!
+//! Times are [`chrono::Local`]: the log stores wall-clock time with its offset, and that wall-clock
+//! time is read back as local time (see [`parse_timestamp`]).
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat};
+
+/// Version of the log line format this crate writes: 1 was kind first (`START|timestamp|activity`),
+/// 2 put the ISO 8601 timestamp first (`ts migrate` converts from 1), 3 added the optional
+/// `;user=NAME` tag in the kind field, 4 the `;parallel` flag for concurrent timers. Bump when
+/// older builds could no longer read new logs, and give `ts migrate` a step for it; readers keep
+/// accepting at least the previous version (see [`Entry::parse`]).
+pub const LOG_FORMAT_VERSION: u32 = 4;
+
+/// Formats a log timestamp using the canonical on-disk representation.
+pub fn format_timestamp(dt: DateTime<Local>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Micros, false)
+}
+
+/// Parses a timestamp field: strict ISO 8601 (RFC 3339) only.
+/// The wall-clock time in the stored offset is treated as local time without
+/// any conversion through UTC.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Local>> {
+    let s = s.trim();
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .and_then(|dt| dt.naive_local().and_local_timezone(Local).single())
+}
+
+/// A user name as it is written in a `;user=NAME` tag: trimmed, with the characters that would
+/// break the field (`|`, `;`, `=`, whitespace) replaced with `_`.
+pub fn clean_user_name(user: &str) -> String {
+    user.trim()
+        .chars()
+        .map(|c| match c {
+            '|' | ';' | '=' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// The `;user=NAME` suffix for the kind field of a log line (see [`clean_user_name`]), or empty
+/// when `user` is `None`.
+pub fn user_attr(user: Option<&str>) -> String {
+    match user {
+        Some(u) => format!(";user={}", clean_user_name(u)),
+        None => String::new(),
+    }
+}
+
+/// The `;context=NAME` suffix for the kind field of a START (where the session was worked, e.g.
+/// `office` or `home`), or empty when `context` is `None`; cleaned like [`user_attr`].
+pub fn context_attr(context: Option<&str>) -> String {
+    match context {
+        Some(c) => user_attr(Some(c)).replacen(";user=", ";context=", 1),
+        None => String::new(),
+    }
+}
+
+/// The `ts context` a kind field records (`START;context=home`), if any.
+pub fn kind_context(field: &str) -> Option<&str> {
+    field
+        .split(';')
+        .skip(1)
+        .find_map(|attr| attr.strip_prefix("context="))
+}
+
+/// Splits the kind field of a log line (`START`, `STOP;user=alice`) into the kind and its user tag.
+pub fn split_kind(field: &str) -> (&str, Option<&str>) {
+    let mut parts = field.split(';');
+    let kind = parts.next().unwrap_or("");
+    let user = parts.find_map(|attr| attr.strip_prefix("user="));
+    (kind, user)
+}
+
+/// Whether a kind field carries the `parallel` flag (`START;parallel|oncall`): a concurrent timer
+/// kept beside the main session instead of replacing it. [`parse_line_for_user`] skips such lines, so the
+/// main START/STOP pairing never sees them.
+pub fn kind_is_parallel(field: &str) -> bool {
+    field.split(';').skip(1).any(|attr| attr == "parallel")
+}
+
+/// The `ts billable` flag in a kind field: `Some(true)` for `START;billable`, `Some(false)` for
+/// `START;billable=no`, `None` when the entry carries neither.
+pub fn kind_billable(field: &str) -> Option<bool> {
+    field.split(';').skip(1).find_map(|attr| match attr {
+        "billable" | "billable=yes" => Some(true),
+        "billable=no" => Some(false),
+        _ => None,
+    })
+}
+
+/// A kind field without its `;mono=` attribute, for an entry whose time is being changed: the
+/// reading no longer belongs to the new time.
+pub fn kind_without_mono(field: &str) -> String {
+    field
+        .split(';')
+        .filter(|attr| !attr.starts_with("mono="))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// A reading of the clock that only moves forward (`;mono=BOOT:SECONDS` in the kind field): an id
+/// of the boot it was taken in and the milliseconds since that boot. Two readings from the same
+/// boot measure the time between their entries exactly, whatever the wall clock did meanwhile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonoStamp {
+    pub boot: String,
+    pub millis: u64,
+}
+
+impl MonoStamp {
+    /// Parses the value of a `;mono=` attribute, e.g. `3f2a9c1b:86400.250`.
+    pub fn parse(value: &str) -> Option<MonoStamp> {
+        let (boot, secs) = value.split_once(':')?;
+        let (whole, frac) = secs.split_once('.').unwrap_or((secs, "0"));
+        let frac: String = frac.chars().chain("000".chars()).take(3).collect();
+        if boot.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(MonoStamp {
+            boot: boot.to_string(),
+            millis: whole.parse::<u64>().ok()? * 1000 + frac.parse::<u64>().ok()?,
+        })
+    }
+
+    /// The `;mono=BOOT:SECONDS` attribute for this reading, with millisecond precision.
+    pub fn attr(&self) -> String {
+        format!(
+            ";mono={}:{}.{:03}",
+            self.boot,
+            self.millis / 1000,
+            self.millis % 1000
+        )
+    }
+}
+
+/// Whether an [`Entry`] starts or stops a session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    Start,
+    Stop,
+}
+
+/// One line of the log, as written:
+/// `timestamp|KIND[;parallel][;billable[=no]][;mono=BOOT:SECONDS][;user=NAME][;context=NAME][|activity]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub time: DateTime<Local>,
+    pub kind: EntryKind,
+    /// The author tag (`;user=NAME`), for logs shared by a team.
+    pub user: Option<String>,
+    /// A parallel timer entry (`;parallel`), kept beside the main session instead of replacing it.
+    pub parallel: bool,
+    /// Set by `ts billable on|off` on a START (`;billable`, `;billable=no`), overriding the
+    /// `billable` categories for that session.
+    pub billable: Option<bool>,
+    /// The monotonic clock when `ts` wrote the entry, if `monotonic_clock` was on.
+    pub mono: Option<MonoStamp>,
+    /// Where a START's session was worked (`;context=NAME`), from `ts context` or detected.
+    pub context: Option<String>,
+    /// The activity (`code` or `code|description`); empty on a main-track STOP.
+    pub activity: String,
+}
+
+impl Entry {
+    /// Parses a log line; `None` for blank lines, `#` comments, `META` lines and anything else that
+    /// is not a START or STOP with a valid timestamp. Format-1 lines are read too.
+    pub fn parse(line: &str) -> Option<Entry> {
+        let mut parts = line.trim().splitn(3, '|');
+        let Some(time) = parse_timestamp(parts.next()?) else {
+            return Entry::parse_version_1(line.trim());
+        };
+        let field = parts.next()?;
+        let (kind, user) = split_kind(field);
+        let kind = match kind {
+            "START" => EntryKind::Start,
+            "STOP" => EntryKind::Stop,
+            _ => return None,
+        };
+        Some(Entry {
+            time,
+            kind,
+            user: user.map(str::to_string),
+            parallel: kind_is_parallel(field),
+            billable: kind_billable(field),
+            mono: field
+                .split(';')
+                .skip(1)
+                .find_map(|attr| MonoStamp::parse(attr.strip_prefix("mono=")?)),
+            context: kind_context(field).map(str::to_string),
+            activity: parts.next().unwrap_or("").to_string(),
+        })
+    }
+
+    /// A format-1 line, kind first: `START|timestamp|activity` or `STOP|timestamp`, the timestamp in
+    /// ISO 8601 or Unix epoch seconds. Read so old logs work before `ts migrate` converts them.
+    fn parse_version_1(line: &str) -> Option<Entry> {
+        let mut parts = line.splitn(3, '|');
+        let kind = match parts.next()? {
+            "START" => EntryKind::Start,
+            "STOP" => EntryKind::Stop,
+            _ => return None,
+        };
+        let stamp = parts.next()?.trim();
+        let time = parse_timestamp(stamp).or_else(|| {
+            let secs = stamp.parse::<i64>().ok()?;
+            Some(DateTime::from_timestamp(secs, 0)?.with_timezone(&Local))
+        })?;
+        let activity = match kind {
+            EntryKind::Start => parts.next().unwrap_or(""),
+            EntryKind::Stop => "",
+        };
+        Some(Entry {
+            time,
+            kind,
+            user: None,
+            parallel: false,
+            billable: None,
+            mono: None,
+            context: None,
+            activity: activity.to_string(),
+        })
+    }
+
+    /// The log line for this entry, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        let kind = match self.kind {
+            EntryKind::Start => "START",
+            EntryKind::Stop => "STOP",
+        };
+        let mut line = format!(
+            "{}|{}{}{}{}{}{}",
+            format_timestamp(self.time),
+            kind,
+            if self.parallel { ";parallel" } else { "" },
+            match self.billable {
+                Some(true) => ";billable",
+                Some(false) => ";billable=no",
+                None => "",
+            },
+            self.mono.as_ref().map(MonoStamp::attr).unwrap_or_default(),
+            user_attr(self.user.as_deref()),
+            context_attr(self.context.as_deref())
+        );
+        if self.kind == EntryKind::Start || self.parallel {
+            line.push('|');
+            line.push_str(&self.activity);
+        }
+        line
+    }
+}
+
+/// The key and value of a `META|key|value` line, the header that records facts about a log
+/// (`version`, `timezone`, `user`, ...); `None` for any other line. Like `#` comments, such lines are
+/// skipped by [`Entry::parse`] and kept verbatim by every command that rewrites a log.
+pub fn parse_meta_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim().strip_prefix("META|")?;
+    let (key, value) = rest.split_once('|').unwrap_or((rest, ""));
+    Some((key.trim(), value.trim()))
+}
+
+/// The `META` fields of a log, in file order.
+pub fn log_meta(content: &str) -> Vec<(&str, &str)> {
+    content.lines().filter_map(parse_meta_line).collect()
+}
+
+/// The format version of a log: its `META|version` when that is a number, else 1 if any entry is
+/// laid out kind first, else the oldest version that has every feature it uses (3 for user tags,
+/// 4 for parallel timers, 2 otherwise).
+pub fn log_format_version(content: &str) -> u32 {
+    if let Some(v) = log_meta(content)
+        .into_iter()
+        .rev()
+        .find_map(|(k, v)| (k == "version").then(|| v.parse().ok()).flatten())
+    {
+        return v;
+    }
+    let mut version = 2;
+    for line in content.lines() {
+        let Some(entry) = Entry::parse(line) else {
+            continue;
+        };
+        if line
+            .trim()
+            .split('|')
+            .next()
+            .and_then(parse_timestamp)
+            .is_none()
+        {
+            return 1;
+        }
+        if entry.parallel {
+            version = version.max(4);
+        } else if entry.user.is_some() {
+            version = version.max(3);
+        }
+    }
+    version
+}
+
+/// Whether `line` is a `#` comment or a `META` line rather than an entry.
+pub fn is_annotation_line(line: &str) -> bool {
+    line.trim_start().starts_with('#') || parse_meta_line(line).is_some()
+}
+
+/// A single parsed line from the timesheet log.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LogLine {
+    /// `timestamp|START|activity`
+    Start(DateTime<Local>, String),
+    /// `timestamp|STOP`
+    Stop(DateTime<Local>),
+}
+
+/// The main-track [`LogLine`] of a log line, skipping parallel timer entries. With `Some(user)` only
+/// lines tagged `;user=user` are kept; with `None` every line is.
+pub fn parse_line_for_user(s: &str, user: Option<&str>) -> Option<LogLine> {
+    let entry = Entry::parse(s)?;
+    if entry.parallel || (user.is_some() && entry.user.as_deref() != user) {
+        return None;
+    }
+    match entry.kind {
+        EntryKind::Start => Some(LogLine::Start(entry.time, entry.activity)),
+        EntryKind::Stop => Some(LogLine::Stop(entry.time)),
+    }
+}
+
+/// The main-track [`LogLine`]s of `content` with their 1-based line numbers, `user` filtering as for
+/// [`parse_line_for_user`]. Where an entry and the one before it carry [`MonoStamp`]s from the same
+/// boot, the monotonic clock wins: a STOP ends exactly the measured time after the entry before it,
+/// and an entry the wall clock put earlier than that one (it was set back) is moved after it.
+pub fn log_lines_for_user(content: &str, user: Option<&str>) -> Vec<(usize, LogLine)> {
+    let mut lines = Vec::new();
+    let mut previous: Option<(DateTime<Local>, MonoStamp)> = None;
+    for (i, line) in content.lines().enumerate() {
+        let Some(entry) = Entry::parse(line) else {
+            continue;
+        };
+        if entry.parallel || (user.is_some() && entry.user.as_deref() != user) {
+            continue;
+        }
+        let mut time = entry.time;
+        if let (Some((prev_time, prev)), Some(mono)) = (&previous, &entry.mono) {
+            if prev.boot == mono.boot && mono.millis >= prev.millis {
+                let measured =
+                    *prev_time + chrono::Duration::milliseconds((mono.millis - prev.millis) as i64);
+                if entry.kind == EntryKind::Stop || time < *prev_time {
+                    time = measured;
+                }
+            }
+        }
+        previous = entry.mono.map(|mono| (time, mono));
+        lines.push((
+            i + 1,
+            match entry.kind {
+                EntryKind::Start => LogLine::Start(time, entry.activity),
+                EntryKind::Stop => LogLine::Stop(time),
+            },
+        ));
+    }
+    lines
+}
+
+/// A parallel timer entry: `start` is false for its STOP.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParallelEvent {
+    pub dt: DateTime<Local>,
+    pub start: bool,
+    pub activity: String,
+}
+
+/// The parallel timer entries in `content`, in file order; `user` filters as for
+/// [`parse_line_for_user`].
+pub fn parse_parallel_events(content: &str, user: Option<&str>) -> Vec<ParallelEvent> {
+    content
+        .lines()
+        .filter_map(Entry::parse)
+        .filter(|e| e.parallel && (user.is_none() || e.user.as_deref() == user))
+        .map(|e| ParallelEvent {
+            dt: e.time,
+            start: e.kind == EntryKind::Start,
+            activity: e.activity,
+        })
+        .collect()
+}
+
+/// The `ts billable` flags of the main-track STARTs in `content`, by start time; `user` filters as
+/// for [`parse_line_for_user`].
+pub fn parse_billable_flags(
+    content: &str,
+    user: Option<&str>,
+) -> std::collections::BTreeMap<DateTime<Local>, bool> {
+    content
+        .lines()
+        .filter_map(Entry::parse)
+        .filter(|e| e.kind == EntryKind::Start && !e.parallel)
+        .filter(|e| user.is_none() || e.user.as_deref() == user)
+        .filter_map(|e| Some((e.time, e.billable?)))
+        .collect()
+}
+
+/// A `ts interrupt` line, `timestamp|INTERRUPT[;user=NAME]|reason`: something that broke your focus
+/// during a session without ending it. Session parsing skips these lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interruption {
+    pub time: DateTime<Local>,
+    pub user: Option<String>,
+    pub reason: String,
+}
+
+impl Interruption {
+    /// Parses an `INTERRUPT` line; `None` for anything else.
+    pub fn parse(line: &str) -> Option<Interruption> {
+        let mut parts = line.trim().splitn(3, '|');
+        let time = parse_timestamp(parts.next()?)?;
+        let (kind, user) = split_kind(parts.next()?);
+        (kind == "INTERRUPT").then(|| Interruption {
+            time,
+            user: user.map(str::to_string),
+            reason: parts.next().unwrap_or("").to_string(),
+        })
+    }
+
+    /// The log line for this interruption, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}|INTERRUPT{}|{}",
+            format_timestamp(self.time),
+            user_attr(self.user.as_deref()),
+            self.reason
+        )
+    }
+}
+
+/// The interruptions in `content`, in file order; `user` filters as for [`parse_line_for_user`].
+pub fn parse_interruptions(content: &str, user: Option<&str>) -> Vec<Interruption> {
+    content
+        .lines()
+        .filter_map(Interruption::parse)
+        .filter(|i| user.is_none() || i.user.as_deref() == user)
+        .collect()
+}
+
+/// Parallel timers still running after `events`: `(activity, start)` in start order.
+pub fn running_parallel(events: &[ParallelEvent]) -> Vec<(String, DateTime<Local>)> {
+    let mut running: Vec<(String, DateTime<Local>)> = Vec::new();
+    for e in events {
+        let pos = running.iter().position(|(a, _)| *a == e.activity);
+        match (e.start, pos) {
+            (true, None) => running.push((e.activity.clone(), e.dt)),
+            (false, Some(i)) => {
+                running.remove(i);
+            }
+            _ => {}
+        }
+    }
+    running
+}
+
+/// Splits a stored activity into its reporting code and optional free-text description
+/// (`DEV|refactoring the billing module`); only the code is used as a report key.
+pub fn split_activity(activity: &str) -> (&str, Option<&str>) {
+    match activity.split_once('|') {
+        Some((code, description)) => (code, Some(description)),
+        None => (activity, None),
+    }
+}
+
+/// An activity for session-level output: the code, with the description in parentheses if any.
+pub fn display_activity(activity: &str) -> String {
+    match split_activity(activity) {
+        (code, Some(description)) => format!("{} ({})", code, description),
+        (code, None) => code.to_string(),
+    }
+}
+
+/// A work session: a START paired (LIFO, as in the report) with the following STOP or START.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub activity: String,
+}
+
+/// All sessions with a positive duration, in the order they end; an open session ends at `virtual_stop`.
+pub fn log_sessions(
+    lines: &[(usize, LogLine)],
+    virtual_stop: Option<DateTime<Local>>,
+) -> Vec<Session> {
+    let mut sessions = Vec::new();
+    let mut stack: Vec<(DateTime<Local>, String)> = Vec::new();
+    let mut close = |stack: &mut Vec<(DateTime<Local>, String)>, end: DateTime<Local>| {
+        if let Some((start, activity)) = stack.pop() {
+            if end > start {
+                sessions.push(Session {
+                    start,
+                    end,
+                    activity,
+                });
+            }
+        }
+    };
+    for (_, ll) in lines {
+        match ll {
+            LogLine::Start(dt, a) => {
+                close(&mut stack, *dt);
+                stack.push((*dt, a.clone()));
+            }
+            LogLine::Stop(dt) => close(&mut stack, *dt),
+        }
+    }
+    if let Some(vstop) = virtual_stop {
+        close(&mut stack, vstop);
+    }
+    sessions
+}
+
+/// Parallel timer sessions from `events`, paired per activity; a timer still running ends at
+/// `virtual_stop` (or is dropped without one).
+pub fn parallel_sessions(
+    events: &[ParallelEvent],
+    virtual_stop: Option<DateTime<Local>>,
+) -> Vec<Session> {
+    let mut sessions = Vec::new();
+    let mut running: Vec<(String, DateTime<Local>)> = Vec::new();
+    for e in events {
+        let pos = running.iter().position(|(a, _)| *a == e.activity);
+        match (e.start, pos) {
+            (true, None) => running.push((e.activity.clone(), e.dt)),
+            (false, Some(i)) => {
+                let (activity, start) = running.remove(i);
+                if e.dt > start {
+                    sessions.push(Session {
+                        start,
+                        end: e.dt,
+                        activity,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(vstop) = virtual_stop {
+        for (activity, start) in running {
+            if vstop > start {
+                sessions.push(Session {
+                    start,
+                    end: vstop,
+                    activity,
+                });
+            }
+        }
+    }
+    sessions
+}
+
+/// Splits overlapping time evenly: each stretch where `n` sessions run at once counts `1/n` of its
+/// length for each (as a shortened session at the stretch's start), so totals equal wall-clock time.
+pub fn split_overlapping_sessions(sessions: &[Session]) -> Vec<Session> {
+    let mut points: Vec<DateTime<Local>> = sessions.iter().flat_map(|s| [s.start, s.end]).collect();
+    points.sort();
+    points.dedup();
+    let mut out = Vec::new();
+    for w in points.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let active: Vec<&Session> = sessions
+            .iter()
+            .filter(|s| s.start <= a && s.end >= b)
+            .collect();
+        if active.is_empty() {
+            continue;
+        }
+        let share = (b - a) / active.len() as i32;
+        for s in active {
+            out.push(Session {
+                start: a,
+                end: a + share,
+                activity: s.activity.clone(),
+            });
+        }
+    }
+    out
+}
+
+/// Seconds worked per activity code and per local start date: everything a report needs besides
+/// the open session (and the unit `ts` caches per log file).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub by_activity: std::collections::BTreeMap<String, i64>,
+    pub by_day: std::collections::BTreeMap<NaiveDate, i64>,
+    /// Seconds per activity code recorded but left out of the two totals above.
+    pub excluded: std::collections::BTreeMap<String, i64>,
+    /// Seconds per activity code of sessions flagged by `ts billable on` (part of `by_activity`).
+    pub billable: std::collections::BTreeMap<String, i64>,
+    /// Seconds per activity code of sessions flagged by `ts billable off` (part of `by_activity`).
+    pub non_billable: std::collections::BTreeMap<String, i64>,
+}
+
+impl Report {
+    /// Adds the time from `start_dt` to `end_dt` (nothing if not positive) to the activity's code
+    /// and, split at midnight (see [`seconds_by_date`]), to the dates it fell on.
+    pub fn add(&mut self, start_dt: DateTime<Local>, end_dt: DateTime<Local>, activity: &str) {
+        let dur = rounded_seconds(end_dt - start_dt);
+        if dur > 0 {
+            let code = split_activity(activity).0;
+            *self.by_activity.entry(code.to_string()).or_insert(0) += dur;
+            for (date, sec) in seconds_by_date(start_dt, end_dt) {
+                *self.by_day.entry(date).or_insert(0) += sec;
+            }
+        }
+    }
+
+    /// Totals `sessions` in full (overlapping ones count for each; see
+    /// [`split_overlapping_sessions`]).
+    pub fn from_sessions(sessions: &[Session]) -> Report {
+        let mut report = Report::default();
+        for s in sessions {
+            report.add(s.start, s.end, &s.activity);
+        }
+        report
+    }
+}
+
+/// `d` in whole seconds, rounded to the nearest rather than truncated, so a run of short sessions
+/// is not undercounted.
+pub fn rounded_seconds(d: chrono::Duration) -> i64 {
+    (d.num_milliseconds() + 500).div_euclid(1000)
+}
+
+/// The seconds from `start` to `end` on each local date they cover, in date order, so a session
+/// past midnight counts toward the day each part fell on (22:00-02:00 is 2h on each). The parts
+/// add up to the whole duration in [`rounded_seconds`]. Empty unless `end` is after `start`.
+pub fn seconds_by_date(start: DateTime<Local>, end: DateTime<Local>) -> Vec<(NaiveDate, i64)> {
+    let total = rounded_seconds(end - start);
+    let mut parts = Vec::new();
+    let mut from = start;
+    let mut counted = 0;
+    while from < end {
+        let date = from.date_naive();
+        let midnight = date
+            .succ_opt()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .and_then(|d| d.and_local_timezone(Local).earliest())
+            .filter(|m| *m < end);
+        let Some(midnight) = midnight else {
+            parts.push((date, total - counted));
+            break;
+        };
+        let sec = (midnight - from).num_seconds();
+        parts.push((date, sec));
+        counted += sec;
+        from = midnight;
+    }
+    parts
+}
+
+/// Parses a start-time string into a DateTime<Local>; tries strict ISO 8601 first, then several other formats (e.g. `%Y-%m-%d %H:%M`, `%H:%M`, `%I:%M %p`),
+/// then a time on a day of the past week: `yesterday 17:30`, `mon 9:00` (the most recent Monday,
+/// today included).
+pub fn parse_start_time(s: &str) -> Option<DateTime<Local>> {
+    let s = s.trim();
+    if let Some(dt) = parse_timestamp(s) {
+        return Some(dt);
+    }
+    let now = Local::now();
+    let today = now.date_naive();
+    let formats = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%H:%M",
+        "%H:%M:%S",
+        "%m/%d/%Y %H:%M",
+        "%m/%d %H:%M",
+        "%I:%M %p",
+        "%I:%M%p",
+    ];
+    for fmt in formats {
+        if let Ok(ndt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return ndt.and_local_timezone(Local).single();
+        }
+    }
+    if let Ok(t) = NaiveTime::parse_from_str(s, "%H:%M") {
+        return today.and_time(t).and_local_timezone(Local).single();
+    }
+    if let Ok(t) = NaiveTime::parse_from_str(s, "%I:%M %p") {
+        return today.and_time(t).and_local_timezone(Local).single();
+    }
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return d
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .single();
+    }
+    let (day, time) = s.split_once(' ')?;
+    let days_back = match day.to_lowercase().as_str() {
+        "today" => 0,
+        "yesterday" => 1,
+        name => {
+            let weekday: chrono::Weekday = name.parse().ok()?;
+            (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7
+        }
+    };
+    let date = today.checked_sub_days(chrono::Days::new(days_back.into()))?;
+    ["%H:%M", "%H:%M:%S", "%I:%M %p", "%I:%M%p"]
+        .iter()
+        .find_map(|fmt| NaiveTime::parse_from_str(time.trim(), fmt).ok())
+        .and_then(|t| date.and_time(t).and_local_timezone(Local).single())
+}
+
+/// Parse a duration string into seconds. E.g. "3", "3m" -> 180; "100s" -> 100; "1h30m" -> 5400;
+/// "2.5m" -> 150. Bare number is treated as minutes. Units: h, m, s (case-insensitive); fractions
+/// are rounded to the nearest second.
+pub fn parse_interval_duration(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("interval cannot be empty".to_string());
+    }
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut total_secs: f64 = 0.0;
+    let is_number = |b: u8| b.is_ascii_digit() || b == b'.';
+    while i < bytes.len() {
+        while i < bytes.len() && !is_number(bytes[i]) {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            break;
+        }
+        let start = i;
+        while i < bytes.len() && is_number(bytes[i]) {
+            i += 1;
+        }
+        let num: f64 = s[start..i]
+            .parse()
+            .map_err(|_| format!("invalid number in interval: {}", s))?;
+        let unit = if i < bytes.len() {
+            let u = bytes[i];
+            if u == b'h' || u == b'H' || u == b'm' || u == b'M' || u == b's' || u == b'S' {
+                i += 1;
+                u
+            } else {
+                b'm'
+            }
+        } else {
+            b'm'
+        };
+        match unit {
+            b'h' | b'H' => total_secs += num * 3600.0,
+            b'm' | b'M' => total_secs += num * 60.0,
+            b's' | b'S' => total_secs += num,
+            _ => total_secs += num * 60.0,
+        }
+    }
+    let total_secs = total_secs.round() as u64;
+    if total_secs == 0 {
+        return Err("interval must be positive".to_string());
+    }
+    Ok(total_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_log_lines_for_user_trusts_the_monotonic_clock() {
+        let at = |h, m, s| Local.with_ymd_and_hms(2026, 10, 14, h, m, s).unwrap();
+        let line = |dt, kind: &str| format!("{}|{}", format_timestamp(dt), kind);
+        let content = [
+            line(at(9, 0, 0), "START;mono=b1:1000.000|dev"),
+            // The wall clock was set back ten minutes; the session really ran 30m 0.5s.
+            line(at(9, 20, 0), "STOP;mono=b1:2800.500"),
+            line(at(9, 19, 0), "START;mono=b1:2810.000|mail"),
+            line(at(9, 40, 0), "STOP;mono=b2:5.000"),
+        ]
+        .join("\n");
+        let lines = log_lines_for_user(&content, None);
+        let end = at(9, 30, 0) + chrono::Duration::milliseconds(500);
+        assert_eq!(lines[1], (2, LogLine::Stop(end)));
+        assert_eq!(
+            lines[2],
+            (
+                3,
+                LogLine::Start(end + chrono::Duration::milliseconds(9500), "mail".into())
+            )
+        );
+        assert_eq!(lines[3], (4, LogLine::Stop(at(9, 40, 0))), "another boot");
+        assert_eq!(rounded_seconds(chrono::Duration::milliseconds(1500)), 2);
+    }
+
+    #[test]
+    fn test_seconds_by_date_splits_at_midnight() {
+        let at = |d, h| Local.with_ymd_and_hms(2026, 10, d, h, 0, 0).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        assert_eq!(
+            seconds_by_date(at(14, 22), at(15, 2)),
+            vec![(day(14), 2 * 3600), (day(15), 2 * 3600)]
+        );
+        assert_eq!(
+            seconds_by_date(at(14, 22), at(16, 1)),
+            vec![(day(14), 2 * 3600), (day(15), 24 * 3600), (day(16), 3600)]
+        );
+        assert_eq!(seconds_by_date(at(14, 9), at(14, 9)), vec![]);
+        let mut report = Report::default();
+        report.add(at(14, 22), at(15, 2), "dev");
+        assert_eq!(report.by_day.get(&day(15)), Some(&(2 * 3600)));
+        assert_eq!(report.by_activity.get("dev"), Some(&(4 * 3600)));
+    }
+
+    #[test]
+    fn test_entry_parse_and_to_line_round_trip() {
+        let line = "2026-10-14T09:00:00.000000+00:00|START;parallel;user=pat|oncall|pager";
+        let entry = Entry::parse(line).unwrap();
+        assert_eq!(entry.kind, EntryKind::Start);
+        assert_eq!(entry.user.as_deref(), Some("pat"));
+        assert!(entry.parallel);
+        assert_eq!(split_activity(&entry.activity), ("oncall", Some("pager")));
+        assert_eq!(entry.to_line(), line);
+        assert_eq!(parse_line_for_user(line, None), None);
+
+        let stop = Entry {
+            time: Local.with_ymd_and_hms(2026, 10, 14, 17, 0, 0).unwrap(),
+            kind: EntryKind::Stop,
+            user: None,
+            parallel: false,
+            billable: None,
+            mono: None,
+            context: None,
+            activity: String::new(),
+        };
+        assert_eq!(Entry::parse(&stop.to_line()), Some(stop.clone()));
+        assert_eq!(
+            parse_line_for_user(&stop.to_line(), Some("pat")),
+            None,
+            "untagged lines are not pat's"
+        );
+        let mono = "2026-10-14T09:00:00.000000+00:00|STOP;mono=3f2a9c1b:86400.025";
+        assert_eq!(
+            Entry::parse(mono).unwrap().mono,
+            Some(MonoStamp {
+                boot: "3f2a9c1b".to_string(),
+                millis: 86_400_025
+            })
+        );
+        assert_eq!(Entry::parse(mono).unwrap().to_line(), mono);
+        assert_eq!(
+            kind_without_mono("START;billable;mono=3f2a9c1b:1.000;user=bob"),
+            "START;billable;user=bob"
+        );
+        let flagged = "2026-10-14T09:00:00.000000+00:00|START;billable=no;user=pat|acme";
+        assert_eq!(Entry::parse(flagged).unwrap().billable, Some(false));
+        assert_eq!(Entry::parse(flagged).unwrap().to_line(), flagged);
+        assert_eq!(Entry::parse("# comment"), None);
+        assert_eq!(Entry::parse("2026-10-14T09:00:00+00:00|PAUSE"), None);
+    }
+}
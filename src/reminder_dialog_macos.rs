@@ -1,9 +1,15 @@
 //! Native macOS reminder dialog using a custom NSPanel with vertical NSStackView of buttons.
-//! Used when the daemon spawns `ts --reminder-dialog choice1 choice2 ...` via launchctl asuser.
+//! Used when the daemon spawns `ts --reminder-dialog choice1 choice2 ...` via launchctl asuser, or,
+//! to skip that launch on every reminder, served by one long-lived `ts --reminder-dialog-helper`
+//! that keeps the NSApplication running and takes each prompt over a Unix socket.
 //! Custom panel guarantees vertical layout regardless of choice count (NSAlert switches to horizontal).
+//! A search field above the buttons fuzzy-filters the activities as you type. With the field empty,
+//! 1-9 pick the first nine activities, Return the most recent one, and Escape snoozes.
+//! Both panels open on the display with the mouse pointer, float above other windows and join every
+//! Space (including a full-screen app's), so a reminder is never left on a Space the user is not on.
 
 use objc2::rc::Retained;
-use objc2::runtime::{AnyObject, Bool, ProtocolObject};
+use objc2::runtime::{AnyObject, Bool, ProtocolObject, Sel};
 use objc2::{define_class, msg_send, AnyThread, MainThreadMarker, MainThreadOnly};
 use objc2_app_kit::{
     NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate, NSAutoresizingMaskOptions,
@@ -12,21 +18,180 @@ use objc2_app_kit::{
     NSUserInterfaceLayoutOrientation, NSView, NSWindow, NSWindowDelegate, NSWindowStyleMask,
 };
 use objc2_foundation::{
-    NSNotification, NSObject, NSObjectProtocol, NSPoint, NSRect, NSSize, NSString,
+    NSArray, NSNotification, NSObject, NSObjectProtocol, NSPoint, NSRect, NSSize, NSString,
 };
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::io::{self, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::PathBuf;
+use std::time::Duration;
 
 // NSUserInterfaceLayoutOrientationVertical = 1
 const NS_USER_INTERFACE_LAYOUT_ORIENTATION_VERTICAL: NSUserInterfaceLayoutOrientation =
     NSUserInterfaceLayoutOrientation(1);
 
+// NSWindowCollectionBehaviorCanJoinAllSpaces (1 << 0) | NSWindowCollectionBehaviorFullScreenAuxiliary
+// (1 << 8): shown on every Space, full-screen ones included.
+const NS_WINDOW_COLLECTION_BEHAVIOR_ALL_SPACES: usize = (1 << 0) | (1 << 8);
+
+// NSFloatingWindowLevel = 3
+const NS_FLOATING_WINDOW_LEVEL: isize = 3;
+
+/// Printed instead of a choice when a full-screen app is frontmost and the caller asked for
+/// [`run_native_reminder_dialog`] to step aside then; the daemon shows a compact alert instead.
+pub const FULL_SCREEN: &str = "FullScreen";
+
 thread_local! {
     static DIALOG_RESULT: RefCell<Option<String>> = const { RefCell::new(None) };
     static INPUT_CONFIRMED: RefCell<bool> = const { RefCell::new(false) };
+    /// The search field, its buttons (one per choice, in order) and the stack holding them.
+    static SEARCH: RefCell<Option<SearchState>> = const { RefCell::new(None) };
+}
+
+/// What the search field's handler needs to filter the buttons and re-lay out the stack.
+struct SearchState {
+    field: Retained<TSReminderInputField>,
+    buttons: Vec<Retained<NSButton>>,
+    stack: Retained<NSStackView>,
+    /// Height of the stack's container; the stack is pinned to its top.
+    doc_height: f64,
+    stack_x: f64,
+    button_width: f64,
+}
+
+/// Height of one button row in the stack (24pt button + 8pt spacing).
+const BUTTON_ROW_HEIGHT: f64 = 32.0;
+
+/// The button stack is never shorter than this, however few buttons it shows.
+const MIN_STACK_HEIGHT: f64 = 160.0;
+
+/// Buttons that stay visible whatever is typed.
+const FIXED_CHOICES: [&str; 3] = ["Stop Work", "Don't Bug Me", "Enter new activity..."];
+
+/// Printed for Escape: dismiss without an answer and ask again at the next reminder.
+const SNOOZE: &str = "Snooze";
+
+/// Indices into `choices` of the activities (everything but the fixed buttons), in display order.
+fn activity_indices(choices: &[String]) -> Vec<usize> {
+    (0..choices.len())
+        .filter(|&i| !FIXED_CHOICES.contains(&choices[i].as_str()))
+        .collect()
+}
+
+/// Button title for `choices[i]`: the first nine activities are numbered for their shortcut.
+fn button_title(choices: &[String], i: usize) -> String {
+    match activity_indices(choices).iter().position(|&a| a == i) {
+        Some(n) if n < 9 => format!("{}  {}", n + 1, choices[i]),
+        _ => choices[i].clone(),
+    }
+}
+
+/// The choice for a digit typed into the empty search field: 1-9 name the first nine activities.
+fn shortcut_choice(choices: &[String], typed: &str) -> Option<String> {
+    let n = match typed.as_bytes() {
+        [d @ b'1'..=b'9'] => usize::from(d - b'1'),
+        _ => return None,
+    };
+    activity_indices(choices)
+        .get(n)
+        .map(|&i| choices[i].clone())
+}
+
+/// The most recent activity, which Return picks with the search field empty. Activities are listed
+/// least recent first, so it is the last one.
+fn default_choice(choices: &[String]) -> Option<String> {
+    activity_indices(choices)
+        .last()
+        .map(|&i| choices[i].clone())
+}
+
+/// Which of `choices` to show for `query`: every activity the fuzzy filter keeps, plus the fixed
+/// buttons; everything for an empty query.
+fn search_visibility(choices: &[String], query: &str) -> Vec<bool> {
+    let query = query.trim();
+    if query.is_empty() {
+        return vec![true; choices.len()];
+    }
+    let matches = crate::fuzzy_filter(choices, query);
+    choices
+        .iter()
+        .map(|c| FIXED_CHOICES.contains(&c.as_str()) || matches.contains(c))
+        .collect()
+}
+
+/// Return in the search field: the best-matching activity, else the typed text as a new activity;
+/// the most recent activity when nothing is typed.
+fn search_pick(choices: &[String], query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return default_choice(choices);
+    }
+    let activities: Vec<String> = choices
+        .iter()
+        .filter(|c| !FIXED_CHOICES.contains(&c.as_str()))
+        .cloned()
+        .collect();
+    crate::fuzzy_filter(&activities, query)
+        .into_iter()
+        .next()
+        .or_else(|| Some(query.to_string()))
+}
+
+/// End the dialog with `choice` as its answer.
+fn finish_with(choice: String) {
+    DIALOG_RESULT.with(|r| *r.borrow_mut() = Some(choice));
+    NSApplication::sharedApplication(MainThreadMarker::new().unwrap()).stopModal();
+}
+
+/// Show only the buttons matching the search text and pin the shrunken stack to the top.
+fn apply_search_filter() {
+    SEARCH.with(|search| {
+        let search = search.borrow();
+        let Some(state) = search.as_ref() else {
+            return;
+        };
+        let choices = &current_choices();
+        let query: Retained<NSString> = unsafe { msg_send![&*state.field, stringValue] };
+        let query = query.to_string();
+        if let Some(choice) = shortcut_choice(choices, &query) {
+            finish_with(choice);
+            return;
+        }
+        let visible = search_visibility(choices, &query);
+        for (btn, show) in state.buttons.iter().zip(&visible) {
+            btn.setHidden(!show);
+        }
+        let shown = visible.iter().filter(|v| **v).count() as f64;
+        let height = (shown * BUTTON_ROW_HEIGHT).max(MIN_STACK_HEIGHT);
+        state.stack.setFrame(NSRect::new(
+            NSPoint::new(state.stack_x, state.doc_height - height),
+            NSSize::new(state.button_width, height),
+        ));
+    });
+}
+
+thread_local! {
+    /// The choices of the dialog being shown; replaced for each prompt a helper serves.
+    static CHOICES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    /// Panel title: the question, then the today/week summary when there is one.
+    static TITLE: RefCell<String> = const { RefCell::new(String::new()) };
+    /// The helper's socket while `ts --reminder-dialog-helper` is starting up.
+    static HELPER: RefCell<Option<UnixListener>> = const { RefCell::new(None) };
+    /// Whether the helper should wait for another prompt (the daemon is still running).
+    static KEEP_RUNNING: Cell<Option<fn() -> bool>> = const { Cell::new(None) };
+}
+
+fn current_choices() -> Vec<String> {
+    CHOICES.with(|c| c.borrow().clone())
+}
+
+/// Make `prompt` and `choices` the next dialog's title and buttons.
+fn set_dialog(prompt: &str, choices: Vec<String>) {
+    CHOICES.with(|c| *c.borrow_mut() = choices);
+    TITLE.with(|t| *t.borrow_mut() = prompt.lines().rev().collect::<Vec<_>>().join(" \u{2014} "));
+    DIALOG_RESULT.with(|r| *r.borrow_mut() = None);
 }
 
-static CHOICES: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
 /// Icon path for dock (ts-icon.svg/png next to exe, or assets/icon.svg when running from repo).
 static ICON_PATH: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
 
@@ -44,12 +209,110 @@ fn is_command_v_shortcut(modifiers: NSEventModifierFlags, key: &str) -> bool {
 }
 
 /// Run the native reminder dialog. Must be called from the main thread (e.g. when invoked as `ts --reminder-dialog ...`).
-/// Returns the selected choice string, or None if cancelled/error.
-pub fn run_native_reminder_dialog(choices: Vec<String>) -> Option<String> {
+/// `prompt` is the question, possibly below a today/week summary line; its lines become the panel
+/// title. Returns the selected choice string, or None if cancelled/error. With
+/// `alert_over_full_screen`, returns [`FULL_SCREEN`] without showing anything when a full-screen app
+/// is frontmost.
+pub fn run_native_reminder_dialog(
+    prompt: &str,
+    choices: Vec<String>,
+    alert_over_full_screen: bool,
+) -> Option<String> {
     let mtm = MainThreadMarker::new()?;
-    CHOICES.set(choices).ok()?;
-    DIALOG_RESULT.with(|r| *r.borrow_mut() = None);
-    // Resolve icon path once: next to exe (ts-icon.svg / ts-icon.png) or repo assets/icon.svg.
+    set_dialog(prompt, choices);
+    resolve_icon_path();
+
+    let app = NSApplication::sharedApplication(mtm);
+    app.setActivationPolicy(NSApplicationActivationPolicy::Prohibited);
+    // Checked before activating, which would bring this app's own Space forward.
+    if alert_over_full_screen && full_screen_app_frontmost() {
+        return Some(FULL_SCREEN.to_string());
+    }
+    app.activate();
+
+    let allocated = ReminderDialogDelegate::alloc(mtm);
+    let delegate: Retained<ReminderDialogDelegate> = unsafe { msg_send![allocated, init] };
+    app.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+
+    app.run();
+
+    DIALOG_RESULT.with(|r| r.borrow_mut().take())
+}
+
+/// Run as the persistent dialog helper (`ts --reminder-dialog-helper`): keep one NSApplication
+/// alive and answer each prompt the daemon sends to `listener`, so a reminder appears without
+/// launching a process. Returns once `keep_running` says the daemon is gone. Must be called from
+/// the main thread.
+pub fn run_reminder_dialog_helper(listener: UnixListener, keep_running: fn() -> bool) {
+    let Some(mtm) = MainThreadMarker::new() else {
+        return;
+    };
+    if listener.set_nonblocking(true).is_err() {
+        return;
+    }
+    resolve_icon_path();
+    KEEP_RUNNING.with(|k| k.set(Some(keep_running)));
+    HELPER.with(|h| *h.borrow_mut() = Some(listener));
+
+    let app = NSApplication::sharedApplication(mtm);
+    app.setActivationPolicy(NSApplicationActivationPolicy::Prohibited);
+    let allocated = ReminderDialogDelegate::alloc(mtm);
+    let delegate: Retained<ReminderDialogDelegate> = unsafe { msg_send![allocated, init] };
+    app.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+
+    app.run();
+}
+
+/// How often an idle helper checks for a prompt (and whether the daemon is still running).
+const HELPER_POLL: Duration = Duration::from_millis(200);
+
+/// The next prompt's connection, or `None` once the daemon is gone. The helper has no window
+/// between prompts, so blocking the main thread here freezes nothing the user can see.
+fn next_dialog_request(listener: &UnixListener) -> Option<UnixStream> {
+    let keep_running = KEEP_RUNNING.with(Cell::get)?;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false).ok()?;
+                return Some(stream);
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                if !keep_running() {
+                    return None;
+                }
+                std::thread::sleep(HELPER_POLL);
+            }
+            Err(_) => return None,
+        }
+    }
+}
+
+/// Answer prompts until the daemon is gone: each connection carries one request (see
+/// [`crate::decode_dialog_request`]) and gets the chosen line back, or nothing if dismissed.
+fn serve_dialog_requests(mtm: MainThreadMarker, app: &NSApplication, listener: UnixListener) {
+    while let Some(mut stream) = next_dialog_request(&listener) {
+        let mut request = String::new();
+        if stream.read_to_string(&mut request).is_err() {
+            continue;
+        }
+        let Some((alert, prompt, choices)) = crate::decode_dialog_request(&request) else {
+            continue;
+        };
+        let answer = if alert && full_screen_app_frontmost() {
+            Some(FULL_SCREEN.to_string())
+        } else {
+            set_dialog(&prompt, choices);
+            show_reminder_panel(mtm, app);
+            DIALOG_RESULT.with(|r| r.borrow_mut().take())
+        };
+        if let Some(answer) = answer {
+            let _ = writeln!(stream, "{}", answer);
+        }
+    }
+}
+
+/// Resolve the dock icon once: next to exe (ts-icon.svg / ts-icon.png) or repo assets/icon.svg.
+fn resolve_icon_path() {
     let _ = ICON_PATH.set(
         std::env::current_exe()
             .ok()
@@ -66,21 +329,10 @@ pub fn run_native_reminder_dialog(choices: Vec<String>) -> Option<String> {
                 })
             }),
     );
-
-    let app = NSApplication::sharedApplication(mtm);
-    app.setActivationPolicy(NSApplicationActivationPolicy::Prohibited);
-    app.activate();
-
-    let allocated = ReminderDialogDelegate::alloc(mtm);
-    let delegate: Retained<ReminderDialogDelegate> = unsafe { msg_send![allocated, init] };
-    app.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
-
-    app.run();
-
-    DIALOG_RESULT.with(|r| r.borrow_mut().take())
 }
 
-// Content view that swallows all keystrokes; only mouse clicks and scrolling work.
+// Content view that swallows all keystrokes except those for the search field (typing and
+// Cmd-V), so no key can press a button; otherwise only mouse clicks and scrolling work.
 define_class!(
     #[unsafe(super(NSView))]
     #[thread_kind = MainThreadOnly]
@@ -89,17 +341,18 @@ define_class!(
 
     impl TSReminderContentView {
         #[unsafe(method(performKeyEquivalent:))]
-        fn perform_key_equivalent(&self, _event: &NSEvent) -> bool {
+        fn perform_key_equivalent(&self, event: &NSEvent) -> bool {
+            let field = SEARCH.with(|s| s.borrow().as_ref().map(|state| state.field.clone()));
+            if let Some(field) = field {
+                let _: Bool = unsafe { msg_send![&*field, performKeyEquivalent: event] };
+            }
             true
         }
         #[unsafe(method(keyDown:))]
         fn key_down(&self, _event: &NSEvent) {}
+        /// Clicking the background leaves the keyboard focus in the search field.
         #[unsafe(method(acceptsFirstResponder))]
         fn accepts_first_responder(&self) -> bool {
-            true
-        }
-        #[unsafe(method(resignFirstResponder))]
-        fn resign_first_responder(&self) -> bool {
             false
         }
     }
@@ -107,6 +360,50 @@ define_class!(
     unsafe impl NSObjectProtocol for TSReminderContentView {}
 );
 
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "TSReminderSearchHandler"]
+    struct TSReminderSearchHandler;
+
+    impl TSReminderSearchHandler {
+        /// Sent by the search field (as its delegate) after every edit.
+        #[unsafe(method(controlTextDidChange:))]
+        fn control_text_did_change(&self, _notification: &NSNotification) {
+            apply_search_filter();
+        }
+
+        /// Return in the search field picks the best match (or the typed text as a new activity).
+        #[unsafe(method(searchSubmitted:))]
+        fn search_submitted(&self, sender: Option<&NSTextField>) {
+            let Some(field) = sender else {
+                return;
+            };
+            let query: Retained<NSString> = unsafe { msg_send![field, stringValue] };
+            if let Some(pick) = search_pick(&current_choices(), &query.to_string()) {
+                finish_with(pick);
+            }
+        }
+
+        /// Escape in the search field snoozes the reminder.
+        #[unsafe(method(control:textView:doCommandBySelector:))]
+        fn do_command_by_selector(
+            &self,
+            _control: &AnyObject,
+            _text_view: &AnyObject,
+            selector: Sel,
+        ) -> Bool {
+            if selector == objc2::sel!(cancelOperation:) {
+                finish_with(SNOOZE.to_string());
+                return true.into();
+            }
+            false.into()
+        }
+    }
+
+    unsafe impl NSObjectProtocol for TSReminderSearchHandler {}
+);
+
 define_class!(
     #[unsafe(super(NSObject))]
     #[thread_kind = MainThreadOnly]
@@ -116,11 +413,13 @@ define_class!(
     impl TSReminderButtonHandler {
         #[unsafe(method(choiceClicked:))]
         fn choice_clicked(&self, sender: Option<&NSButton>) {
-            if let Some(btn) = sender {
-                let title = btn.title().to_string();
-                DIALOG_RESULT.with(|r| *r.borrow_mut() = Some(title));
-                let app = NSApplication::sharedApplication(MainThreadMarker::new().unwrap());
-                app.stopModal();
+            // The tag is the button's index into CHOICES (its title may carry a shortcut number).
+            let choice = sender.and_then(|btn| {
+                let index = usize::try_from(btn.tag()).ok()?;
+                CHOICES.with(|c| c.borrow().get(index).cloned())
+            });
+            if let Some(choice) = choice {
+                finish_with(choice);
             }
         }
     }
@@ -205,6 +504,46 @@ define_class!(
     unsafe impl NSObjectProtocol for TSReminderInputField {}
 );
 
+/// Index of the frame in `frames` that contains `point` (screen coordinates), if any.
+fn screen_containing(frames: &[NSRect], point: NSPoint) -> Option<usize> {
+    frames.iter().position(|f| {
+        point.x >= f.origin.x
+            && point.x < f.origin.x + f.size.width
+            && point.y >= f.origin.y
+            && point.y < f.origin.y + f.size.height
+    })
+}
+
+/// Frame of the display the user is on (the one with the mouse pointer), else the main screen.
+fn active_screen_frame(mtm: MainThreadMarker) -> NSRect {
+    let mouse: NSPoint = unsafe { msg_send![objc2::class!(NSEvent), mouseLocation] };
+    let screens: Retained<NSArray<NSScreen>> =
+        unsafe { msg_send![objc2::class!(NSScreen), screens] };
+    let frames: Vec<NSRect> = (0..screens.count())
+        .map(|i| screens.objectAtIndex(i).frame())
+        .collect();
+    screen_containing(&frames, mouse)
+        .map(|i| frames[i])
+        .or_else(|| NSScreen::mainScreen(mtm).map(|s| s.frame()))
+        .unwrap_or_else(|| NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(800.0, 600.0)))
+}
+
+/// Keeps `panel` above other windows and on every Space, so switching Spaces or a full-screen app
+/// cannot hide it.
+fn float_on_all_spaces(panel: &NSPanel) {
+    let _: () = unsafe {
+        msg_send![panel, setCollectionBehavior: NS_WINDOW_COLLECTION_BEHAVIOR_ALL_SPACES]
+    };
+    let _: () = unsafe { msg_send![panel, setLevel: NS_FLOATING_WINDOW_LEVEL] };
+}
+
+/// Whether a full-screen app is frontmost: its Space hides the menu bar. (A menu bar set to hide
+/// automatically looks the same.)
+fn full_screen_app_frontmost() -> bool {
+    let visible: Bool = unsafe { msg_send![objc2::class!(NSMenu), menuBarVisible] };
+    !visible.as_bool()
+}
+
 fn centered_rect(screen_frame: NSRect, width: f64, height: f64) -> NSRect {
     let x = screen_frame.origin.x + (screen_frame.size.width - width) / 2.0;
     let y = screen_frame.origin.y + (screen_frame.size.height - height) / 2.0;
@@ -214,9 +553,7 @@ fn centered_rect(screen_frame: NSRect, width: f64, height: f64) -> NSRect {
 fn run_native_enter_activity_dialog(mtm: MainThreadMarker, app: &NSApplication) -> Option<String> {
     INPUT_CONFIRMED.with(|confirmed| *confirmed.borrow_mut() = false);
 
-    let screen_frame = NSScreen::mainScreen(mtm)
-        .map(|s| s.frame())
-        .unwrap_or_else(|| NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(800.0, 600.0)));
+    let screen_frame = active_screen_frame(mtm);
     let panel_alloc = NSPanel::alloc(mtm);
     let panel: Retained<NSPanel> = NSPanel::initWithContentRect_styleMask_backing_defer(
         panel_alloc,
@@ -227,6 +564,7 @@ fn run_native_enter_activity_dialog(mtm: MainThreadMarker, app: &NSApplication)
     );
     panel.setTitle(&NSString::from_str("Enter activity"));
     unsafe { panel.setReleasedWhenClosed(false) };
+    float_on_all_spaces(&panel);
 
     let content_rect = panel.contentRectForFrameRect(panel.frame());
     let content_alloc = NSView::alloc(mtm);
@@ -319,6 +657,223 @@ fn run_native_enter_activity_dialog(mtm: MainThreadMarker, app: &NSApplication)
     }
 }
 
+/// Build the reminder panel for the current choices and run it modally until the user answers,
+/// leaving the answer in `DIALOG_RESULT`. The panel is hidden again afterwards, so a helper can
+/// show the next one.
+fn show_reminder_panel(mtm: MainThreadMarker, app: &NSApplication) {
+    let choices = &current_choices();
+    if choices.is_empty() {
+        return;
+    }
+
+    // Regular: visible in dock and Cmd-Tab so the user can reach the dialog.
+    app.setActivationPolicy(NSApplicationActivationPolicy::Regular);
+    // Use timesheet icon in dock when available.
+    if let Some(Some(path)) = ICON_PATH.get() {
+        if path.exists() {
+            if let Some(s) = path.to_str() {
+                let ns_path = NSString::from_str(s);
+                if let Some(image) = NSImage::initWithContentsOfFile(NSImage::alloc(), &ns_path) {
+                    unsafe { app.setApplicationIconImage(Some(&image)) };
+                }
+            }
+        }
+    }
+    #[allow(deprecated)]
+    app.activateIgnoringOtherApps(true);
+
+    // Create handler for button clicks.
+    let handler_alloc = TSReminderButtonHandler::alloc(mtm);
+    let handler: Retained<TSReminderButtonHandler> = unsafe { msg_send![handler_alloc, init] };
+    let sel_choice_clicked = objc2::sel!(choiceClicked:);
+
+    // Panel: the whole display the user is on.
+    let screen_frame = active_screen_frame(mtm);
+    let style = NSWindowStyleMask::Titled; // No Closable: only button-clicks dismiss
+    let panel_alloc = NSPanel::alloc(mtm);
+    let panel: Retained<NSPanel> = NSPanel::initWithContentRect_styleMask_backing_defer(
+        panel_alloc,
+        NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(320.0, 400.0)),
+        style,
+        NSBackingStoreType::Buffered,
+        false,
+    );
+    panel.setFrame_display(screen_frame, true);
+    let title = TITLE.with(|t| t.borrow().clone());
+    let title = if title.is_empty() {
+        "What are you working on?".to_string()
+    } else {
+        title
+    };
+    panel.setTitle(&NSString::from_str(&title));
+    unsafe { panel.setReleasedWhenClosed(false) };
+    float_on_all_spaces(&panel);
+    let panel_delegate_alloc = TSReminderPanelDelegate::alloc(mtm);
+    let panel_delegate: Retained<TSReminderPanelDelegate> =
+        unsafe { msg_send![panel_delegate_alloc, init] };
+    panel.setDelegate(Some(ProtocolObject::from_ref(&*panel_delegate)));
+
+    // Content view: fill panel content area (resize with window).
+    // TSReminderContentView swallows keystrokes; only mouse and scroll work.
+    let content_rect = panel.contentRectForFrameRect(screen_frame);
+    let content_alloc = TSReminderContentView::alloc(mtm);
+    let content: Retained<TSReminderContentView> =
+        unsafe { msg_send![content_alloc, initWithFrame: content_rect] };
+    content.setAutoresizingMask(
+        NSAutoresizingMaskOptions::ViewWidthSizable
+            | NSAutoresizingMaskOptions::ViewHeightSizable
+            | NSAutoresizingMaskOptions::ViewMinXMargin
+            | NSAutoresizingMaskOptions::ViewMaxXMargin
+            | NSAutoresizingMaskOptions::ViewMinYMargin
+            | NSAutoresizingMaskOptions::ViewMaxYMargin,
+    );
+    panel.setContentView(Some(&content));
+
+    // Search field across the top; the buttons scroll below it.
+    let search_height: f64 = 28.0;
+    let search_alloc = TSReminderInputField::alloc(mtm);
+    let search: Retained<TSReminderInputField> = unsafe {
+        msg_send![
+            search_alloc,
+            initWithFrame: NSRect::new(
+                NSPoint::new(20.0, content_rect.size.height - 20.0 - search_height),
+                NSSize::new(content_rect.size.width - 40.0, search_height),
+            )
+        ]
+    };
+    search.setEditable(true);
+    search.setSelectable(true);
+    search.setPlaceholderString(Some(&NSString::from_str(
+        "Type to filter; 1-9 pick, Return the most recent, Esc snoozes",
+    )));
+    search.setAutoresizingMask(
+        NSAutoresizingMaskOptions::ViewWidthSizable | NSAutoresizingMaskOptions::ViewMinYMargin,
+    );
+    let search_handler_alloc = TSReminderSearchHandler::alloc(mtm);
+    let search_handler: Retained<TSReminderSearchHandler> =
+        unsafe { msg_send![search_handler_alloc, init] };
+    let _: () = unsafe { msg_send![&*search, setDelegate: &*search_handler] };
+    let _: () = unsafe { msg_send![&*search, setTarget: &*search_handler] };
+    let _: () = unsafe { msg_send![&*search, setAction: objc2::sel!(searchSubmitted:)] };
+    // Tab stays in the field, so no key can reach a button.
+    let _: () = unsafe { msg_send![&*search, setNextKeyView: &*search] };
+    content.addSubview(&search);
+    let search_field: &TSReminderInputField = search.as_ref();
+    let search_text: &NSTextField = search_field.as_ref();
+    let search_view: &NSView = search_text.as_ref();
+    panel.setInitialFirstResponder(Some(search_view));
+
+    // Vertical stack for buttons. Height = ~32pt per button (24pt + 8pt spacing).
+    let button_width: f64 = 280.0;
+    let button_height: f64 = BUTTON_ROW_HEIGHT;
+    let stack_height = (choices.len() as f64 * button_height).max(MIN_STACK_HEIGHT);
+    let stack_frame = NSRect::new(
+        NSPoint::new(0.0, 0.0),
+        NSSize::new(button_width, stack_height),
+    );
+    let stack_alloc = NSStackView::alloc(mtm);
+    let stack: Retained<NSStackView> =
+        unsafe { msg_send![stack_alloc, initWithFrame: stack_frame] };
+    stack.setOrientation(NS_USER_INTERFACE_LAYOUT_ORIENTATION_VERTICAL);
+    stack.setSpacing(8.0);
+    stack.setDistribution(NSStackViewDistribution::FillEqually);
+
+    let mut buttons = Vec::with_capacity(choices.len());
+    let default = default_choice(choices);
+    for (i, choice) in choices.iter().enumerate() {
+        let btn = unsafe {
+            NSButton::buttonWithTitle_target_action(
+                &NSString::from_str(&button_title(choices, i)),
+                Some(handler.as_ref() as &AnyObject),
+                Some(sel_choice_clicked),
+                mtm,
+            )
+        };
+        btn.setTag(i as isize);
+        if default.as_ref() == Some(choice) {
+            // Drawn as the default button; Return reaches it through the search field.
+            btn.setKeyEquivalent(&NSString::from_str("\r"));
+        }
+        stack.addArrangedSubview(&btn);
+        buttons.push(btn);
+    }
+
+    // Container to center the stack horizontally within the scroll area.
+    let scroll_width = content_rect.size.width - 40.0;
+    let scroll_height = content_rect.size.height - 40.0 - search_height - 12.0;
+    let doc_height = scroll_height.max(stack_height);
+    let stack_center_x = (scroll_width - button_width) / 2.0;
+    let container_frame = NSRect::new(
+        NSPoint::new(0.0, 0.0),
+        NSSize::new(scroll_width, doc_height),
+    );
+    let container_alloc = NSView::alloc(mtm);
+    let container: Retained<NSView> =
+        unsafe { msg_send![container_alloc, initWithFrame: container_frame] };
+    stack.setFrame(NSRect::new(
+        NSPoint::new(stack_center_x, doc_height - stack_height),
+        NSSize::new(button_width, stack_height),
+    ));
+    container.addSubview(&stack);
+
+    // Scroll view: fill content (with insets for padding).
+    let scroll_frame = NSRect::new(
+        NSPoint::new(20.0, 20.0),
+        NSSize::new(scroll_width, scroll_height),
+    );
+    let scroll_alloc = NSScrollView::alloc(mtm);
+    let scroll: Retained<NSScrollView> =
+        unsafe { msg_send![scroll_alloc, initWithFrame: scroll_frame] };
+    scroll.setAutoresizingMask(
+        NSAutoresizingMaskOptions::ViewWidthSizable
+            | NSAutoresizingMaskOptions::ViewHeightSizable
+            | NSAutoresizingMaskOptions::ViewMinXMargin
+            | NSAutoresizingMaskOptions::ViewMaxXMargin
+            | NSAutoresizingMaskOptions::ViewMinYMargin
+            | NSAutoresizingMaskOptions::ViewMaxYMargin,
+    );
+    scroll.setDocumentView(Some(&container));
+    scroll.setHasVerticalScroller(true);
+    scroll.setHasHorizontalScroller(false);
+    scroll.setAutohidesScrollers(true);
+    content.addSubview(&scroll);
+    SEARCH.with(|s| {
+        *s.borrow_mut() = Some(SearchState {
+            field: search.clone(),
+            buttons,
+            stack: stack.clone(),
+            doc_height,
+            stack_x: stack_center_x,
+            button_width,
+        })
+    });
+    panel.orderFrontRegardless();
+    let _: bool = panel.makeFirstResponder(Some(search_text.as_ref()));
+
+    // Re-show if dismissed without a button choice (e.g. process killed).
+    loop {
+        DIALOG_RESULT.with(|r| *r.borrow_mut() = None);
+        let _ = app.runModalForWindow(&panel);
+        match DIALOG_RESULT.with(|r| r.borrow().clone()) {
+            Some(selected) if selected == "Enter new activity..." => {
+                let _: () = unsafe { msg_send![&panel, orderOut: None::<&AnyObject>] };
+                if let Some(activity) = run_native_enter_activity_dialog(mtm, app) {
+                    DIALOG_RESULT.with(|r| *r.borrow_mut() = Some(activity));
+                    break;
+                }
+                panel.orderFrontRegardless();
+                let _: bool = panel.makeFirstResponder(Some(search_text.as_ref()));
+            }
+            Some(_) => break,
+            None => panel.orderFrontRegardless(),
+        }
+    }
+
+    let _: () = unsafe { msg_send![&panel, orderOut: None::<&AnyObject>] };
+    SEARCH.with(|s| *s.borrow_mut() = None);
+    app.setActivationPolicy(NSApplicationActivationPolicy::Prohibited);
+}
+
 define_class!(
     #[unsafe(super(NSObject))]
     #[thread_kind = MainThreadOnly]
@@ -331,164 +886,11 @@ define_class!(
         #[unsafe(method(applicationDidFinishLaunching:))]
         fn application_did_finish_launching(&self, _notification: Option<&NSNotification>) {
             let mtm = MainThreadMarker::new().expect("main thread");
-            let choices = match CHOICES.get() {
-                Some(c) => c,
-                None => return,
-            };
-            if choices.is_empty() {
-                return;
-            }
-
-            // Regular: visible in dock and Cmd-Tab so the user can reach the dialog.
             let app = NSApplication::sharedApplication(mtm);
-            app.setActivationPolicy(NSApplicationActivationPolicy::Regular);
-            // Use timesheet icon in dock when available.
-            if let Some(Some(path)) = ICON_PATH.get() {
-                if path.exists() {
-                    if let Some(s) = path.to_str() {
-                        let ns_path = NSString::from_str(s);
-                        if let Some(image) =
-                            NSImage::initWithContentsOfFile(NSImage::alloc(), &ns_path)
-                        {
-                            unsafe { app.setApplicationIconImage(Some(&image)) };
-                        }
-                    }
-                }
+            match HELPER.with(|h| h.borrow_mut().take()) {
+                Some(listener) => serve_dialog_requests(mtm, &app, listener),
+                None => show_reminder_panel(mtm, &app),
             }
-            #[allow(deprecated)]
-            app.activateIgnoringOtherApps(true);
-
-            // Create handler for button clicks.
-            let handler_alloc = TSReminderButtonHandler::alloc(mtm);
-            let handler: Retained<TSReminderButtonHandler> =
-                unsafe { msg_send![handler_alloc, init] };
-            let sel_choice_clicked = objc2::sel!(choiceClicked:);
-
-            // Panel: full screen.
-            let screen_frame = NSScreen::mainScreen(mtm)
-                .map(|s| s.frame())
-                .unwrap_or_else(|| NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(800.0, 600.0)));
-            let style = NSWindowStyleMask::Titled; // No Closable: only button-clicks dismiss
-            let panel_alloc = NSPanel::alloc(mtm);
-            let panel: Retained<NSPanel> = NSPanel::initWithContentRect_styleMask_backing_defer(
-                panel_alloc,
-                NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(320.0, 400.0)),
-                style,
-                NSBackingStoreType::Buffered,
-                false,
-            );
-            panel.setFrame_display(screen_frame, true);
-            panel.setTitle(&NSString::from_str("What are you working on?"));
-            unsafe { panel.setReleasedWhenClosed(false) };
-            let panel_delegate_alloc = TSReminderPanelDelegate::alloc(mtm);
-            let panel_delegate: Retained<TSReminderPanelDelegate> =
-                unsafe { msg_send![panel_delegate_alloc, init] };
-            panel.setDelegate(Some(ProtocolObject::from_ref(&*panel_delegate)));
-
-            // Content view: fill panel content area (resize with window).
-            // TSReminderContentView swallows keystrokes; only mouse and scroll work.
-            let content_rect = panel.contentRectForFrameRect(screen_frame);
-            let content_alloc = TSReminderContentView::alloc(mtm);
-            let content: Retained<TSReminderContentView> =
-                unsafe { msg_send![content_alloc, initWithFrame: content_rect] };
-            content.setAutoresizingMask(
-                NSAutoresizingMaskOptions::ViewWidthSizable
-                    | NSAutoresizingMaskOptions::ViewHeightSizable
-                    | NSAutoresizingMaskOptions::ViewMinXMargin
-                    | NSAutoresizingMaskOptions::ViewMaxXMargin
-                    | NSAutoresizingMaskOptions::ViewMinYMargin
-                    | NSAutoresizingMaskOptions::ViewMaxYMargin,
-            );
-            panel.setContentView(Some(&content));
-            panel.setInitialFirstResponder(Some(content.as_ref() as &NSView));
-
-            // Vertical stack for buttons. Height = ~32pt per button (24pt + 8pt spacing).
-            let button_width: f64 = 280.0;
-            let button_height: f64 = 32.0;
-            let stack_height = (choices.len() as f64 * button_height).max(160.0);
-            let stack_frame = NSRect::new(
-                NSPoint::new(0.0, 0.0),
-                NSSize::new(button_width, stack_height),
-            );
-            let stack_alloc = NSStackView::alloc(mtm);
-            let stack: Retained<NSStackView> =
-                unsafe { msg_send![stack_alloc, initWithFrame: stack_frame] };
-            stack.setOrientation(NS_USER_INTERFACE_LAYOUT_ORIENTATION_VERTICAL);
-            stack.setSpacing(8.0);
-            stack.setDistribution(NSStackViewDistribution::FillEqually);
-
-            for choice in choices.iter() {
-                let btn = unsafe {
-                    NSButton::buttonWithTitle_target_action(
-                        &NSString::from_str(choice),
-                        Some(handler.as_ref() as &AnyObject),
-                        Some(sel_choice_clicked),
-                        mtm,
-                    )
-                };
-                stack.addArrangedSubview(&btn);
-            }
-
-            // Container to center the stack horizontally within the scroll area.
-            let scroll_width = content_rect.size.width - 40.0;
-            let scroll_height = content_rect.size.height - 40.0;
-            let doc_height = scroll_height.max(stack_height);
-            let stack_center_x = (scroll_width - button_width) / 2.0;
-            let container_frame = NSRect::new(
-                NSPoint::new(0.0, 0.0),
-                NSSize::new(scroll_width, doc_height),
-            );
-            let container_alloc = NSView::alloc(mtm);
-            let container: Retained<NSView> =
-                unsafe { msg_send![container_alloc, initWithFrame: container_frame] };
-            stack.setFrame(NSRect::new(
-                NSPoint::new(stack_center_x, doc_height - stack_height),
-                NSSize::new(button_width, stack_height),
-            ));
-            container.addSubview(&stack);
-
-            // Scroll view: fill content (with insets for padding).
-            let scroll_frame = NSRect::new(
-                NSPoint::new(20.0, 20.0),
-                NSSize::new(scroll_width, scroll_height),
-            );
-            let scroll_alloc = NSScrollView::alloc(mtm);
-            let scroll: Retained<NSScrollView> =
-                unsafe { msg_send![scroll_alloc, initWithFrame: scroll_frame] };
-            scroll.setAutoresizingMask(
-                NSAutoresizingMaskOptions::ViewWidthSizable
-                    | NSAutoresizingMaskOptions::ViewHeightSizable
-                    | NSAutoresizingMaskOptions::ViewMinXMargin
-                    | NSAutoresizingMaskOptions::ViewMaxXMargin
-                    | NSAutoresizingMaskOptions::ViewMinYMargin
-                    | NSAutoresizingMaskOptions::ViewMaxYMargin,
-            );
-            scroll.setDocumentView(Some(&container));
-            scroll.setHasVerticalScroller(true);
-            scroll.setHasHorizontalScroller(false);
-            scroll.setAutohidesScrollers(true);
-            content.addSubview(&scroll);
-            panel.orderFrontRegardless();
-
-            // Re-show if dismissed without a button choice (e.g. process killed).
-            loop {
-                DIALOG_RESULT.with(|r| *r.borrow_mut() = None);
-                let _ = app.runModalForWindow(&panel);
-                match DIALOG_RESULT.with(|r| r.borrow().clone()) {
-                    Some(selected) if selected == "Enter new activity..." => {
-                        let _: () = unsafe { msg_send![&panel, orderOut: None::<&AnyObject>] };
-                        if let Some(activity) = run_native_enter_activity_dialog(mtm, &app) {
-                            DIALOG_RESULT.with(|r| *r.borrow_mut() = Some(activity));
-                            break;
-                        }
-                        panel.orderFrontRegardless();
-                    }
-                    Some(_) => break,
-                    None => panel.orderFrontRegardless(),
-                }
-            }
-
-            app.setActivationPolicy(NSApplicationActivationPolicy::Prohibited);
             let _: () = unsafe { msg_send![&app, stop: None::<&AnyObject>] };
         }
     }
@@ -507,6 +909,74 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn search_keeps_fixed_buttons_and_picks_best_match() {
+        let choices: Vec<String> = [
+            "Stop Work",
+            "Don't Bug Me",
+            "PROJ-12 review",
+            "OPS-3 deploy",
+            "Enter new activity...",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+        assert_eq!(search_visibility(&choices, " "), vec![true; 5]);
+        assert_eq!(
+            search_visibility(&choices, "dep"),
+            vec![true, true, false, true, true]
+        );
+        assert_eq!(
+            search_pick(&choices, "p12"),
+            Some("PROJ-12 review".to_string())
+        );
+        assert_eq!(search_pick(&choices, "lunch"), Some("lunch".to_string()));
+        assert_eq!(search_pick(&choices, ""), Some("OPS-3 deploy".to_string()));
+    }
+
+    #[test]
+    fn screen_containing_finds_the_display_under_the_pointer() {
+        let frames = [
+            NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(1440.0, 900.0)),
+            NSRect::new(NSPoint::new(-1920.0, -180.0), NSSize::new(1920.0, 1080.0)),
+        ];
+        assert_eq!(
+            screen_containing(&frames, NSPoint::new(700.0, 450.0)),
+            Some(0)
+        );
+        assert_eq!(
+            screen_containing(&frames, NSPoint::new(-10.0, 800.0)),
+            Some(1)
+        );
+        assert_eq!(screen_containing(&frames, NSPoint::new(1440.0, 10.0)), None);
+    }
+
+    #[test]
+    fn shortcuts_number_the_activities() {
+        let choices: Vec<String> = [
+            "Stop Work",
+            "Don't Bug Me",
+            "PROJ-12 review",
+            "OPS-3 deploy",
+            "Enter new activity...",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+        assert_eq!(button_title(&choices, 0), "Stop Work");
+        assert_eq!(button_title(&choices, 2), "1  PROJ-12 review");
+        assert_eq!(button_title(&choices, 3), "2  OPS-3 deploy");
+        assert_eq!(button_title(&choices, 4), "Enter new activity...");
+        assert_eq!(
+            shortcut_choice(&choices, "2"),
+            Some("OPS-3 deploy".to_string())
+        );
+        assert_eq!(shortcut_choice(&choices, "3"), None);
+        assert_eq!(shortcut_choice(&choices, "0"), None);
+        assert_eq!(shortcut_choice(&choices, "12"), None);
+        assert_eq!(default_choice(&choices[..2]), None);
+    }
+
     #[test]
     fn command_v_shortcut_rejects_other_modifier_combinations() {
         assert!(!is_command_v_shortcut(NSEventModifierFlags::Control, "v"));